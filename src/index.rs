@@ -0,0 +1,306 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use libipld::Ipld;
+use serde_json::Value;
+
+use crate::backend::{Backend, BackendError, Cid};
+use crate::traits::Blockable;
+
+/// Separates a dotted metadata field path from its serialized value inside a
+/// posting-list term, e.g. `author\u{1}"alice"`.
+const TERM_SEPARATOR: char = '\u{1}';
+
+/// A secondary index over `Object` metadata: an inverted index mapping a
+/// `(field_path, value)` posting term to the set of object CIDs whose
+/// metadata carries that field/value pair. Persisted as IPLD like everything
+/// else in the store, so the index is itself content-addressed and can be
+/// rebuilt or shared purely by CID.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Index {
+    postings: BTreeMap<String, Vec<Cid>>,
+}
+
+impl Blockable for Index {
+    type Error = IndexIpldError;
+
+    fn to_ipld(&self) -> Ipld {
+        Ipld::Map(
+            self.postings
+                .iter()
+                .map(|(term, cids)| {
+                    let list = cids.iter().map(|cid| Ipld::Link(cid.clone())).collect();
+                    (term.clone(), Ipld::List(list))
+                })
+                .collect(),
+        )
+    }
+
+    fn from_ipld(ipld: &Ipld) -> Result<Self, Self::Error> {
+        let map = match ipld {
+            Ipld::Map(map) => map,
+            _ => return Err(IndexIpldError::NotMap),
+        };
+
+        let mut postings = BTreeMap::new();
+        for (term, value) in map {
+            let list = match value {
+                Ipld::List(list) => list,
+                _ => return Err(IndexIpldError::NotLinkList(term.clone())),
+            };
+            let mut cids = Vec::with_capacity(list.len());
+            for entry in list {
+                match entry {
+                    Ipld::Link(cid) => cids.push(cid.clone()),
+                    _ => return Err(IndexIpldError::NotLinkList(term.clone())),
+                }
+            }
+            postings.insert(term.clone(), cids);
+        }
+
+        Ok(Self { postings })
+    }
+}
+
+impl Index {
+    /* Queries */
+
+    /// All object CIDs whose metadata has `field` set to exactly `value`.
+    pub fn find(&self, field: &str, value: &Value) -> Vec<Cid> {
+        self.postings
+            .get(&term(field, value))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// All object CIDs whose metadata has a string `field` whose value
+    /// starts with `prefix`.
+    pub fn find_prefix(&self, field: &str, prefix: &str) -> Vec<Cid> {
+        let lower = string_term_prefix(field, prefix);
+        self.postings
+            .range(lower.clone()..)
+            .take_while(|(term, _)| term.starts_with(&lower))
+            .flat_map(|(_, cids)| cids.iter().cloned())
+            .collect()
+    }
+
+    /* Mutation */
+
+    /// Migrate this object's postings from `old_cid` to `new_cid`.
+    ///
+    /// In a content-addressed store every `update()` mints a brand-new CID
+    /// for the whole object, even for fields that didn't change. So it's not
+    /// enough to patch only the terms that differ between `old` and `new`
+    /// metadata — every term in `new` must end up pointing at `new_cid`, and
+    /// every term in `old` that's no longer present in `new` (or that *is*
+    /// still present, but under the superseded `old_cid`) must drop
+    /// `old_cid`. Otherwise unchanged fields keep pointing at a CID that no
+    /// longer represents the object's current state.
+    ///
+    /// `old` is `None` for a genesis write that has no prior metadata/CID to
+    /// migrate away from; the value and its CID are bundled into a single
+    /// tuple (rather than two independently-optional parameters) so a
+    /// caller can't pass one without the other and silently skip the
+    /// removal pass.
+    pub fn patch(&mut self, old: Option<(&Value, &Cid)>, new: &Value, new_cid: &Cid) {
+        let old_terms = old.map(|(old, _)| terms_for(old)).unwrap_or_default();
+        let new_terms = terms_for(new);
+
+        if let Some((_, old_cid)) = old {
+            if old_cid != new_cid {
+                for removed in old_terms.iter() {
+                    if let Some(cids) = self.postings.get_mut(removed) {
+                        cids.retain(|existing| existing != old_cid);
+                        if cids.is_empty() {
+                            self.postings.remove(removed);
+                        }
+                    }
+                }
+            }
+        }
+
+        for added in new_terms.iter() {
+            let cids = self.postings.entry(added.clone()).or_default();
+            if !cids.contains(new_cid) {
+                cids.push(new_cid.clone());
+            }
+        }
+    }
+
+    /* Commit */
+
+    /// Write this index as a new block and advance the root pointer to it,
+    /// returning the [`IndexManifest`] callers should persist as the new
+    /// head of the index.
+    pub fn commit(&self, store: &Backend) -> Result<IndexManifest, BackendError> {
+        let cid = store.put(self)?;
+        Ok(IndexManifest::new(cid))
+    }
+}
+
+/// Flatten `metadata` into dotted-path `(field_path, value)` pairs — nested
+/// objects extend the path with `.key`, array elements with `.index` — and
+/// return the posting term for each leaf.
+fn terms_for(metadata: &Value) -> BTreeSet<String> {
+    let mut leaves = Vec::new();
+    flatten(metadata, "", &mut leaves);
+    leaves
+        .iter()
+        .map(|(field, value)| term(field, value))
+        .collect()
+}
+
+fn flatten(value: &Value, prefix: &str, out: &mut Vec<(String, Value)>) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                let path = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{prefix}.{key}")
+                };
+                flatten(value, &path, out);
+            }
+        }
+        Value::Array(values) => {
+            for (index, value) in values.iter().enumerate() {
+                let path = format!("{prefix}.{index}");
+                flatten(value, &path, out);
+            }
+        }
+        leaf if !prefix.is_empty() => out.push((prefix.to_string(), leaf.clone())),
+        _ => {}
+    }
+}
+
+fn term(field: &str, value: &Value) -> String {
+    format!("{field}{TERM_SEPARATOR}{value}")
+}
+
+fn string_term_prefix(field: &str, prefix: &str) -> String {
+    // `Value::String`'s `Display` impl renders as a quoted JSON string, so a
+    // string-field prefix query has to be quoted the same way to line up
+    // with the lexicographic ordering of stored terms; the trailing quote is
+    // dropped since it isn't part of the prefix being searched for.
+    let quoted = Value::String(prefix.to_string()).to_string();
+    let quoted_prefix = quoted.strip_suffix('"').unwrap_or(quoted.as_str());
+    format!("{field}{TERM_SEPARATOR}{quoted_prefix}")
+}
+
+const INDEX_MANIFEST_ROOT_LABEL: &str = "root";
+
+/// Points at the current root CID of an [`Index`] block, so the whole index
+/// can be located, rebuilt, or shared by a single CID rather than having to
+/// be recomputed from every object in the store. [`Index::commit`] produces
+/// one each time the index is persisted; analogous to
+/// [`crate::snapshot::SnapshotRoot`] for the snapshot chain.
+#[derive(Debug, PartialEq, Eq)]
+pub struct IndexManifest {
+    root: Cid,
+}
+
+impl IndexManifest {
+    pub fn new(root: Cid) -> Self {
+        Self { root }
+    }
+
+    pub fn root(&self) -> &Cid {
+        &self.root
+    }
+}
+
+impl Blockable for IndexManifest {
+    type Error = IndexIpldError;
+
+    fn to_ipld(&self) -> Ipld {
+        let mut map = BTreeMap::new();
+        map.insert(
+            INDEX_MANIFEST_ROOT_LABEL.to_string(),
+            Ipld::Link(self.root.clone()),
+        );
+        Ipld::Map(map)
+    }
+
+    fn from_ipld(ipld: &Ipld) -> Result<Self, Self::Error> {
+        let map = match ipld {
+            Ipld::Map(map) => map,
+            _ => return Err(IndexIpldError::NotMap),
+        };
+
+        let root = match map.get(INDEX_MANIFEST_ROOT_LABEL) {
+            Some(Ipld::Link(root)) => root.clone(),
+            _ => {
+                return Err(IndexIpldError::MissingMapMember(
+                    INDEX_MANIFEST_ROOT_LABEL.to_string(),
+                ))
+            }
+        };
+
+        Ok(Self { root })
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum IndexIpldError {
+    #[error("ipld data is not map")]
+    NotMap,
+    #[error("posting list for term {0} is not a list of links")]
+    NotLinkList(String),
+    #[error("missing map member: {0}")]
+    MissingMapMember(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::str::FromStr;
+
+    const SAMPLE_CID_A: &str = "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi";
+    const SAMPLE_CID_B: &str = "bafybeibz4fhrxqm3cdsz3qyfaq5ummo33s4etfcgswsv3qpc3sjspn5vaq";
+
+    #[test]
+    fn patch_migrates_unchanged_fields_to_the_new_cid() {
+        let cid_a = Cid::from_str(SAMPLE_CID_A).unwrap();
+        let cid_b = Cid::from_str(SAMPLE_CID_B).unwrap();
+
+        let mut index = Index::default();
+        let v1 = json!({ "author": "alice" });
+        index.patch(None, &v1, &cid_a);
+
+        let v2 = json!({ "author": "alice", "tag": "x" });
+        index.patch(Some((&v1, &cid_a)), &v2, &cid_b);
+
+        assert_eq!(index.find("author", &json!("alice")), vec![cid_b.clone()]);
+        assert_eq!(index.find("tag", &json!("x")), vec![cid_b]);
+    }
+
+    #[test]
+    fn patch_drops_fields_removed_by_the_update() {
+        let cid_a = Cid::from_str(SAMPLE_CID_A).unwrap();
+        let cid_b = Cid::from_str(SAMPLE_CID_B).unwrap();
+
+        let mut index = Index::default();
+        let v1 = json!({ "author": "alice", "tag": "x" });
+        index.patch(None, &v1, &cid_a);
+
+        let v2 = json!({ "author": "alice" });
+        index.patch(Some((&v1, &cid_a)), &v2, &cid_b);
+
+        assert_eq!(index.find("author", &json!("alice")), vec![cid_b]);
+        assert!(index.find("tag", &json!("x")).is_empty());
+    }
+
+    #[test]
+    fn commit_writes_a_block_and_returns_a_manifest_pointing_at_it() {
+        let cid_a = Cid::from_str(SAMPLE_CID_A).unwrap();
+
+        let store = Backend::default();
+        let mut index = Index::default();
+        index.patch(None, &json!({ "author": "alice" }), &cid_a);
+
+        let manifest = index.commit(&store).expect("commit index");
+
+        let reloaded: Index = store.get(manifest.root()).expect("get committed index");
+        assert_eq!(reloaded, index);
+    }
+}
@@ -0,0 +1,295 @@
+use std::collections::BTreeMap;
+
+use libipld::Ipld;
+use time::OffsetDateTime;
+
+use crate::backend::{Backend, BackendError, Cid};
+use crate::traits::Blockable;
+
+const SNAPSHOT_ID_LABEL: &str = "snapshot_id";
+const SNAPSHOT_TIMESTAMP_LABEL: &str = "timestamp";
+const SNAPSHOT_MANIFEST_LABEL: &str = "manifest";
+const SNAPSHOT_PARENT_LABEL: &str = "parent";
+
+/// A point-in-time manifest of every live object key in the store, mapping
+/// each key to its current object CID. Snapshots chain via `parent`,
+/// forming a Merkle DAG of store states analogous to [`Object::history`]'s
+/// revision chain, so the whole store gains time-travel reads: any
+/// historical state is reproducible from a single root CID.
+///
+/// [`Object::history`]: crate::types::object::Object::history
+#[derive(Debug, PartialEq, Eq)]
+pub struct Snapshot {
+    snapshot_id: String,
+    timestamp: OffsetDateTime,
+    manifest: BTreeMap<String, Cid>,
+    parent: Option<Cid>,
+}
+
+impl Snapshot {
+    /* Constructors */
+
+    /// Build a new snapshot over `manifest`, chained onto `parent`.
+    pub fn new(snapshot_id: String, manifest: BTreeMap<String, Cid>, parent: Option<Cid>) -> Self {
+        Self {
+            snapshot_id,
+            timestamp: OffsetDateTime::now_utc(),
+            manifest,
+            parent,
+        }
+    }
+
+    /* Getters */
+
+    pub fn snapshot_id(&self) -> &str {
+        &self.snapshot_id
+    }
+
+    pub fn timestamp(&self) -> &OffsetDateTime {
+        &self.timestamp
+    }
+
+    pub fn manifest(&self) -> &BTreeMap<String, Cid> {
+        &self.manifest
+    }
+
+    pub fn parent(&self) -> Option<&Cid> {
+        self.parent.as_ref()
+    }
+
+    /// The object CID recorded for `key` in this snapshot, if it was live.
+    pub fn get(&self, key: &str) -> Option<&Cid> {
+        self.manifest.get(key)
+    }
+
+    /* Commit */
+
+    /// Write this snapshot as a new block and advance the root pointer to
+    /// it, returning the [`SnapshotRoot`] callers should persist as the new
+    /// head of the snapshot chain.
+    pub fn commit(&self, store: &Backend) -> Result<SnapshotRoot, BackendError> {
+        let cid = store.put(self)?;
+        Ok(SnapshotRoot::new(cid))
+    }
+}
+
+impl Blockable for Snapshot {
+    type Error = SnapshotIpldError;
+
+    fn to_ipld(&self) -> Ipld {
+        let mut map = BTreeMap::new();
+
+        map.insert(
+            SNAPSHOT_ID_LABEL.to_string(),
+            Ipld::String(self.snapshot_id.clone()),
+        );
+        map.insert(
+            SNAPSHOT_TIMESTAMP_LABEL.to_string(),
+            Ipld::Integer(self.timestamp.unix_timestamp_nanos()),
+        );
+        map.insert(
+            SNAPSHOT_MANIFEST_LABEL.to_string(),
+            Ipld::Map(
+                self.manifest
+                    .iter()
+                    .map(|(key, cid)| (key.clone(), Ipld::Link(cid.clone())))
+                    .collect(),
+            ),
+        );
+        if let Some(parent) = self.parent() {
+            map.insert(
+                SNAPSHOT_PARENT_LABEL.to_string(),
+                Ipld::Link(parent.clone()),
+            );
+        }
+
+        Ipld::Map(map)
+    }
+
+    fn from_ipld(ipld: &Ipld) -> Result<Self, Self::Error> {
+        let map = match ipld {
+            Ipld::Map(map) => map,
+            _ => return Err(SnapshotIpldError::NotMap),
+        };
+
+        let snapshot_id = match map.get(SNAPSHOT_ID_LABEL) {
+            Some(Ipld::String(snapshot_id)) => snapshot_id.clone(),
+            _ => {
+                return Err(SnapshotIpldError::MissingMapMember(
+                    SNAPSHOT_ID_LABEL.to_string(),
+                ))
+            }
+        };
+
+        let timestamp_int = match map.get(SNAPSHOT_TIMESTAMP_LABEL) {
+            Some(Ipld::Integer(timestamp)) => *timestamp,
+            _ => {
+                return Err(SnapshotIpldError::MissingMapMember(
+                    SNAPSHOT_TIMESTAMP_LABEL.to_string(),
+                ))
+            }
+        };
+        let timestamp = OffsetDateTime::from_unix_timestamp_nanos(timestamp_int)?;
+
+        let manifest_map = match map.get(SNAPSHOT_MANIFEST_LABEL) {
+            Some(Ipld::Map(manifest_map)) => manifest_map,
+            _ => {
+                return Err(SnapshotIpldError::MissingMapMember(
+                    SNAPSHOT_MANIFEST_LABEL.to_string(),
+                ))
+            }
+        };
+        let mut manifest = BTreeMap::new();
+        for (key, value) in manifest_map {
+            match value {
+                Ipld::Link(cid) => {
+                    manifest.insert(key.clone(), cid.clone());
+                }
+                _ => return Err(SnapshotIpldError::NotLink(key.clone())),
+            }
+        }
+
+        let parent = match map.get(SNAPSHOT_PARENT_LABEL) {
+            Some(Ipld::Link(parent)) => Some(parent.clone()),
+            Some(_) => {
+                return Err(SnapshotIpldError::MissingMapMember(
+                    SNAPSHOT_PARENT_LABEL.to_string(),
+                ))
+            }
+            None => None,
+        };
+
+        Ok(Self {
+            snapshot_id,
+            timestamp,
+            manifest,
+            parent,
+        })
+    }
+}
+
+const SNAPSHOT_ROOT_LABEL: &str = "root";
+
+/// Points at the current head of the snapshot chain — the CID of the most
+/// recently committed [`Snapshot`] — so the whole store's latest state (and
+/// every historical state behind it) can be located purely from this CID.
+/// [`Snapshot::commit`] advances this pointer each time a new snapshot is
+/// committed; analogous to [`crate::index::IndexManifest`] for the index.
+#[derive(Debug, PartialEq, Eq)]
+pub struct SnapshotRoot {
+    root: Cid,
+}
+
+impl SnapshotRoot {
+    pub fn new(root: Cid) -> Self {
+        Self { root }
+    }
+
+    pub fn root(&self) -> &Cid {
+        &self.root
+    }
+}
+
+impl Blockable for SnapshotRoot {
+    type Error = SnapshotIpldError;
+
+    fn to_ipld(&self) -> Ipld {
+        let mut map = BTreeMap::new();
+        map.insert(
+            SNAPSHOT_ROOT_LABEL.to_string(),
+            Ipld::Link(self.root.clone()),
+        );
+        Ipld::Map(map)
+    }
+
+    fn from_ipld(ipld: &Ipld) -> Result<Self, Self::Error> {
+        let map = match ipld {
+            Ipld::Map(map) => map,
+            _ => return Err(SnapshotIpldError::NotMap),
+        };
+
+        let root = match map.get(SNAPSHOT_ROOT_LABEL) {
+            Some(Ipld::Link(root)) => root.clone(),
+            _ => {
+                return Err(SnapshotIpldError::MissingMapMember(
+                    SNAPSHOT_ROOT_LABEL.to_string(),
+                ))
+            }
+        };
+
+        Ok(Self { root })
+    }
+}
+
+/// Walk the snapshot chain starting at `head` to find the newest snapshot at
+/// or before `as_of`, and resolve `key` within it. Returns `Ok(None)` if
+/// `key` was never live at or before `as_of`.
+pub fn read_as_of(
+    store: &Backend,
+    head: &Cid,
+    key: &str,
+    as_of: OffsetDateTime,
+) -> Result<Option<Cid>, SnapshotHistoryError> {
+    let mut next = Some(head.clone());
+    while let Some(cid) = next {
+        let snapshot: Snapshot = store.get(&cid)?;
+        if *snapshot.timestamp() <= as_of {
+            return Ok(snapshot.get(key).cloned());
+        }
+        next = snapshot.parent().cloned();
+    }
+    Ok(None)
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SnapshotHistoryError {
+    #[error("backend error: {0}")]
+    Backend(#[from] BackendError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SnapshotIpldError {
+    #[error("invalid datetime: {0}")]
+    InvalidDateTime(#[from] time::error::ComponentRange),
+    #[error("missing map member: {0}")]
+    MissingMapMember(String),
+    #[error("manifest entry for key {0} is not a link")]
+    NotLink(String),
+    #[error("ipld data is not map")]
+    NotMap,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    const SAMPLE_CID: &str = "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi";
+
+    #[test]
+    fn commit_advances_root_and_read_as_of_walks_the_chain() {
+        let store = Backend::default();
+
+        let mut manifest_v1 = BTreeMap::new();
+        manifest_v1.insert("k".to_string(), Cid::default());
+        let snapshot_v1 = Snapshot::new("v1".to_string(), manifest_v1, None);
+        let v1_timestamp = *snapshot_v1.timestamp();
+        let root_v1 = snapshot_v1.commit(&store).expect("commit v1");
+
+        let mut manifest_v2 = BTreeMap::new();
+        manifest_v2.insert("k".to_string(), Cid::from_str(SAMPLE_CID).unwrap());
+        let snapshot_v2 =
+            Snapshot::new("v2".to_string(), manifest_v2, Some(root_v1.root().clone()));
+        let v2_timestamp = *snapshot_v2.timestamp();
+        let root_v2 = snapshot_v2.commit(&store).expect("commit v2");
+
+        assert_eq!(
+            read_as_of(&store, root_v2.root(), "k", v1_timestamp).unwrap(),
+            Some(Cid::default())
+        );
+        assert_eq!(
+            read_as_of(&store, root_v2.root(), "k", v2_timestamp).unwrap(),
+            Some(Cid::from_str(SAMPLE_CID).unwrap())
+        );
+    }
+}
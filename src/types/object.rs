@@ -1,19 +1,39 @@
 use std::collections::BTreeMap;
+use std::str::FromStr;
 
 use libipld::Ipld;
+use mime::Mime;
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use serde_json::{Number, Value};
+use time::format_description::well_known::Rfc3339;
 use time::OffsetDateTime;
 
-use crate::backend::Cid;
+use crate::backend::{Backend, BackendError, Cid};
 use crate::traits::Blockable;
 
+/// Key of the single-entry `{"/": "<cid>"}` wrapper object that, like a bare
+/// CID string in object metadata, is escaped to an `Ipld::Link` on encode,
+/// mirroring the conventional IPLD JSON representation of a link.
+const IPLD_LINK_ESCAPE_KEY: &str = "/";
+
+/// Note on `metadata`: any string it contains that happens to parse as a
+/// [`Cid`] — and any single-key `{"/": "<cid>"}` object — is canonicalized to
+/// an `Ipld::Link` on encode (see [`value_to_ipld`]), and every link decodes
+/// back to a bare CID string (see [`ipld_to_value`]). This is lossy: an
+/// opaque string that coincidentally matches CID syntax changes meaning
+/// across a round trip, and the `{"/": ...}` wrapper shape is never
+/// reconstructed. Don't rely on `metadata() == original_value` after a
+/// round trip through [`Blockable::to_ipld`]/[`Blockable::from_ipld`] if the
+/// metadata may contain CID-shaped strings.
 #[derive(Debug, PartialEq, Eq)]
 pub struct Object {
     created_at: OffsetDateTime,
     updated_at: OffsetDateTime,
     data: Cid,
     metadata: Value,
+    content_type: Mime,
+    previous: Option<Cid>,
+    timestamp_encoding: TimestampEncoding,
 }
 
 impl Default for Object {
@@ -23,6 +43,9 @@ impl Default for Object {
             updated_at: OffsetDateTime::now_utc(),
             data: Cid::default(),
             metadata: Value::Null,
+            content_type: mime::APPLICATION_OCTET_STREAM,
+            previous: None,
+            timestamp_encoding: TimestampEncoding::default(),
         }
     }
 }
@@ -31,31 +54,59 @@ const OBJECT_CREATED_AT_LABEL: &str = "created_at";
 const OBJECT_UPDATED_AT_LABEL: &str = "updated_at";
 const OBJECT_DATA_LABEL: &str = "data";
 const OBJECT_METADATA_LABEL: &str = "metadata";
+const OBJECT_CONTENT_TYPE_LABEL: &str = "content_type";
+const OBJECT_PREVIOUS_LABEL: &str = "previous";
+
+/// Wire encoding used for `created_at`/`updated_at` when serializing an
+/// `Object` to IPLD. Decoding always auto-detects the encoding a block was
+/// written with, so either mode remains readable regardless of which one a
+/// given `to_ipld` call chose.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TimestampEncoding {
+    /// Compact `Ipld::Integer` of `unix_timestamp_nanos`.
+    #[default]
+    UnixNanos,
+    /// Human-readable `Ipld::String` in RFC 3339 form, for interop with
+    /// other IPLD-over-IPFS tooling that stores times this way.
+    Rfc3339,
+}
+
+fn encode_timestamp(
+    dt: &OffsetDateTime,
+    encoding: TimestampEncoding,
+) -> Result<Ipld, time::error::Format> {
+    match encoding {
+        TimestampEncoding::UnixNanos => Ok(Ipld::Integer(dt.unix_timestamp_nanos())),
+        TimestampEncoding::Rfc3339 => Ok(Ipld::String(dt.format(&Rfc3339)?)),
+    }
+}
+
+fn decode_timestamp(ipld: Option<&Ipld>, label: &str) -> Result<OffsetDateTime, ObjectIpldError> {
+    match ipld {
+        Some(Ipld::Integer(nanos)) => Ok(OffsetDateTime::from_unix_timestamp_nanos(*nanos)?),
+        Some(Ipld::String(s)) => {
+            OffsetDateTime::parse(s, &Rfc3339).map_err(ObjectIpldError::InvalidTimestampString)
+        }
+        _ => Err(ObjectIpldError::MissingMapMember(label.to_string())),
+    }
+}
 
 impl Blockable for Object {
     type Error = ObjectIpldError;
 
     fn to_ipld(&self) -> Ipld {
-        let mut map = BTreeMap::new();
-
-        map.insert(
-            OBJECT_CREATED_AT_LABEL.to_string(),
-            Ipld::Integer(self.created_at().unix_timestamp_nanos()),
-        );
-        map.insert(
-            OBJECT_UPDATED_AT_LABEL.to_string(),
-            Ipld::Integer(self.updated_at().unix_timestamp_nanos()),
-        );
-        map.insert(
-            OBJECT_DATA_LABEL.to_string(),
-            Ipld::Link(self.data().clone()),
-        );
-        let metadata_string = self.metadata().to_string();
-        map.insert(
-            OBJECT_METADATA_LABEL.to_string(),
-            Ipld::String(metadata_string),
-        );
-        Ipld::Map(map)
+        // Honors the encoding set via `Object::set_timestamp_encoding` (or
+        // detected on decode), so the normal store path (`Backend::put`)
+        // can actually write `Rfc3339` blocks rather than only being
+        // reachable through `to_ipld_with_timestamp_encoding` directly.
+        // Falls back to the always-infallible `UnixNanos` encoding if
+        // `Rfc3339` formatting fails (a timestamp's year outside
+        // `0000..=9999`), since `Blockable::to_ipld` can't return an error.
+        self.to_ipld_with_timestamp_encoding(self.timestamp_encoding)
+            .unwrap_or_else(|_| {
+                self.to_ipld_with_timestamp_encoding(TimestampEncoding::UnixNanos)
+                    .expect("UnixNanos timestamp encoding is infallible")
+            })
     }
 
     fn from_ipld(ipld: &Ipld) -> Result<Self, Self::Error> {
@@ -64,54 +115,168 @@ impl Blockable for Object {
             _ => return Err(ObjectIpldError::NotMap),
         };
 
-        let created_at_int = match map.get(OBJECT_CREATED_AT_LABEL) {
-            Some(Ipld::Integer(created_at)) => created_at.clone(),
+        let created_at =
+            decode_timestamp(map.get(OBJECT_CREATED_AT_LABEL), OBJECT_CREATED_AT_LABEL)?;
+        let updated_at =
+            decode_timestamp(map.get(OBJECT_UPDATED_AT_LABEL), OBJECT_UPDATED_AT_LABEL)?;
+        // Re-encoding on a subsequent `to_ipld` preserves whichever mode this
+        // block was written in, rather than silently switching it.
+        let timestamp_encoding = match map.get(OBJECT_CREATED_AT_LABEL) {
+            Some(Ipld::String(_)) => TimestampEncoding::Rfc3339,
+            _ => TimestampEncoding::UnixNanos,
+        };
+
+        let data = match map.get(OBJECT_DATA_LABEL) {
+            Some(Ipld::Link(data)) => data.clone(),
             _ => {
                 return Err(ObjectIpldError::MissingMapMember(
-                    OBJECT_CREATED_AT_LABEL.to_string(),
+                    OBJECT_DATA_LABEL.to_string(),
                 ))
             }
         };
-        let created_at = OffsetDateTime::from_unix_timestamp_nanos(created_at_int)?;
 
-        let updated_at_int = match map.get(OBJECT_UPDATED_AT_LABEL) {
-            Some(Ipld::Integer(updated_at)) => updated_at.clone(),
-            _ => {
+        let metadata_ipld = match map.get(OBJECT_METADATA_LABEL) {
+            Some(metadata_ipld) => metadata_ipld,
+            None => {
                 return Err(ObjectIpldError::MissingMapMember(
-                    OBJECT_UPDATED_AT_LABEL.to_string(),
+                    OBJECT_METADATA_LABEL.to_string(),
                 ))
             }
         };
-        let updated_at = OffsetDateTime::from_unix_timestamp_nanos(updated_at_int)?;
+        let metadata = ipld_to_value(metadata_ipld);
 
-        let data = match map.get(OBJECT_DATA_LABEL) {
-            Some(Ipld::Link(data)) => data.clone(),
-            _ => {
-                return Err(ObjectIpldError::MissingMapMember(
-                    OBJECT_DATA_LABEL.to_string(),
-                ))
+        let content_type = match map.get(OBJECT_CONTENT_TYPE_LABEL) {
+            Some(Ipld::String(content_type)) if content_type.is_empty() => {
+                mime::APPLICATION_OCTET_STREAM
+            }
+            Some(Ipld::String(content_type)) => content_type.parse::<Mime>()?,
+            None => mime::APPLICATION_OCTET_STREAM,
+            Some(other) => {
+                return Err(ObjectIpldError::ContentTypeNotString(format!("{other:?}")))
             }
         };
 
-        let metadata_string = match map.get(OBJECT_METADATA_LABEL) {
-            Some(Ipld::String(metadata_string)) => metadata_string,
-            _ => {
+        let previous = match map.get(OBJECT_PREVIOUS_LABEL) {
+            Some(Ipld::Link(previous)) => Some(previous.clone()),
+            Some(_) => {
                 return Err(ObjectIpldError::MissingMapMember(
-                    OBJECT_METADATA_LABEL.to_string(),
+                    OBJECT_PREVIOUS_LABEL.to_string(),
                 ))
             }
+            None => None,
         };
-        let metadata: Value = serde_json::from_str(&metadata_string)?;
 
         Ok(Self {
             created_at,
             updated_at,
             data,
             metadata,
+            content_type,
+            previous,
+            timestamp_encoding,
         })
     }
 }
 
+/// Recursively convert a JSON [`Value`] into [`Ipld`], escaping CID-shaped
+/// strings (and `{"/": "<cid>"}` wrapper objects) as [`Ipld::Link`] so
+/// metadata can reference other objects in the DAG.
+///
+/// This canonicalization is lossy and not opt-in: *any* string that happens
+/// to parse as a [`Cid`] is escaped, not just ones deliberately wrapped in
+/// `{"/": ...}`, and [`ipld_to_value`] always decodes a link back to a bare
+/// CID string rather than restoring the original shape. A metadata value
+/// containing a CID-shaped string is therefore not guaranteed to compare
+/// equal to itself after a round trip.
+fn value_to_ipld(value: &Value) -> Ipld {
+    match value {
+        Value::Null => Ipld::Null,
+        Value::Bool(b) => Ipld::Bool(*b),
+        Value::Number(n) => number_to_ipld(n),
+        Value::String(s) => match Cid::from_str(s) {
+            Ok(cid) => Ipld::Link(cid),
+            Err(_) => Ipld::String(s.clone()),
+        },
+        Value::Array(values) => Ipld::List(values.iter().map(value_to_ipld).collect()),
+        Value::Object(object) => {
+            if let Some(Value::String(s)) = object.get(IPLD_LINK_ESCAPE_KEY) {
+                if object.len() == 1 {
+                    if let Ok(cid) = Cid::from_str(s) {
+                        return Ipld::Link(cid);
+                    }
+                }
+            }
+            Ipld::Map(
+                object
+                    .iter()
+                    .map(|(k, v)| (k.clone(), value_to_ipld(v)))
+                    .collect(),
+            )
+        }
+    }
+}
+
+/// Convert a JSON [`Number`] to [`Ipld::Integer`] or [`Ipld::Float`],
+/// preserving the original integer-vs-float distinction losslessly.
+fn number_to_ipld(n: &Number) -> Ipld {
+    if let Some(i) = n.as_i64() {
+        Ipld::Integer(i as i128)
+    } else if let Some(u) = n.as_u64() {
+        Ipld::Integer(u as i128)
+    } else {
+        // Not representable as an i64/u64 (e.g. a fractional value, or a
+        // float stored by serde_json as `Number`); fall back to f64, which
+        // covers the remaining case losslessly for any `Number` we can
+        // construct from JSON source text.
+        Ipld::Float(n.as_f64().unwrap_or(0.0))
+    }
+}
+
+/// Convert an `Ipld::Integer`'s `i128` back into a JSON [`Number`], the
+/// inverse of `number_to_ipld`. `libipld` stores any `i64`/`u64` we might
+/// have encoded as an `i128`, so this has to widen back through the same
+/// `i64`/`u64` boundary rather than truncating with `as i64`, or values
+/// above `i64::MAX` (e.g. `u64::MAX`) would wrap around to negative numbers.
+fn integer_to_value(i: i128) -> Value {
+    if let Ok(i) = i64::try_from(i) {
+        Value::Number(Number::from(i))
+    } else if let Ok(u) = u64::try_from(i) {
+        Value::Number(Number::from(u))
+    } else {
+        // Outside the i64/u64 range entirely; not producible by
+        // `number_to_ipld`, but guard decoding blocks written by other
+        // tooling by falling back to a lossy f64 rather than panicking.
+        Number::from_f64(i as f64)
+            .map(Value::Number)
+            .unwrap_or(Value::Null)
+    }
+}
+
+/// Recursively convert [`Ipld`] back into a JSON [`Value`], the inverse of
+/// [`value_to_ipld`]. A link is decoded back into its plain CID string form.
+fn ipld_to_value(ipld: &Ipld) -> Value {
+    match ipld {
+        Ipld::Null => Value::Null,
+        Ipld::Bool(b) => Value::Bool(*b),
+        Ipld::Integer(i) => integer_to_value(*i),
+        Ipld::Float(f) => Number::from_f64(*f)
+            .map(Value::Number)
+            .unwrap_or(Value::Null),
+        Ipld::String(s) => Value::String(s.clone()),
+        Ipld::Link(cid) => Value::String(cid.to_string()),
+        Ipld::List(list) => Value::Array(list.iter().map(ipld_to_value).collect()),
+        Ipld::Map(map) => Value::Object(
+            map.iter()
+                .map(|(k, v)| (k.clone(), ipld_to_value(v)))
+                .collect(),
+        ),
+        // Bytes aren't produced by `value_to_ipld`, but may appear in blocks
+        // written by other tooling; decode them lossily (as UTF-8) into a
+        // JSON string, which re-encodes as `Ipld::String`, not `Ipld::Bytes`.
+        Ipld::Bytes(bytes) => Value::String(String::from_utf8_lossy(bytes).into_owned()),
+    }
+}
+
 impl Object {
     /* Getters */
 
@@ -131,10 +296,77 @@ impl Object {
         &self.metadata
     }
 
+    pub fn content_type(&self) -> &Mime {
+        &self.content_type
+    }
+
+    pub fn previous(&self) -> Option<&Cid> {
+        self.previous.as_ref()
+    }
+
+    /// The [`TimestampEncoding`] [`Blockable::to_ipld`] will use for this
+    /// object: whatever was set via [`Object::set_timestamp_encoding`], or
+    /// whichever mode the block was decoded from.
+    pub fn timestamp_encoding(&self) -> TimestampEncoding {
+        self.timestamp_encoding
+    }
+
+    /* Encoding */
+
+    /// Like [`Blockable::to_ipld`], but with explicit control over how
+    /// `created_at`/`updated_at` are encoded. [`Blockable::from_ipld`]
+    /// auto-detects whichever encoding was used, so blocks written with
+    /// either remain readable. Fails if `encoding` is [`TimestampEncoding::Rfc3339`]
+    /// and a timestamp's year falls outside the `0000..=9999` range RFC 3339
+    /// can represent.
+    pub fn to_ipld_with_timestamp_encoding(
+        &self,
+        encoding: TimestampEncoding,
+    ) -> Result<Ipld, time::error::Format> {
+        let mut map = BTreeMap::new();
+
+        map.insert(
+            OBJECT_CREATED_AT_LABEL.to_string(),
+            encode_timestamp(self.created_at(), encoding)?,
+        );
+        map.insert(
+            OBJECT_UPDATED_AT_LABEL.to_string(),
+            encode_timestamp(self.updated_at(), encoding)?,
+        );
+        map.insert(
+            OBJECT_DATA_LABEL.to_string(),
+            Ipld::Link(self.data().clone()),
+        );
+        map.insert(
+            OBJECT_METADATA_LABEL.to_string(),
+            value_to_ipld(self.metadata()),
+        );
+        map.insert(
+            OBJECT_CONTENT_TYPE_LABEL.to_string(),
+            Ipld::String(self.content_type().to_string()),
+        );
+        if let Some(previous) = self.previous() {
+            map.insert(
+                OBJECT_PREVIOUS_LABEL.to_string(),
+                Ipld::Link(previous.clone()),
+            );
+        }
+        Ok(Ipld::Map(map))
+    }
+
     /* Updaters */
 
-    /// Update the data, metadata or both
-    pub fn update(&mut self, data: Option<Cid>, metadata: Option<Value>) {
+    /// Update the data, metadata, content type or any combination thereof.
+    /// Each parameter is `None` to leave that field unchanged.
+    ///
+    /// This does not touch `previous` — call [`Object::link_previous`]
+    /// separately to record the edit in the revision chain.
+    pub fn update(
+        &mut self,
+        data: Option<Cid>,
+        metadata: Option<Value>,
+        content_type: Option<Mime>,
+    ) {
         self.updated_at = OffsetDateTime::now_utc();
         match data {
             Some(cid) => self.data = cid,
@@ -144,7 +376,78 @@ impl Object {
             Some(value) => self.metadata = value,
             None => {}
         }
+        match content_type {
+            Some(mime) => self.content_type = mime,
+            None => {}
+        }
     }
+
+    /// Link this `Object` back to `previous` — the CID of the block it was
+    /// read from before the update — so the edit can be walked via
+    /// [`Object::history`]. Unlike [`Object::update`]'s parameters, this
+    /// unconditionally overwrites `previous`; a genesis write with no prior
+    /// stored block simply never calls it.
+    pub fn link_previous(&mut self, previous: Cid) {
+        self.previous = Some(previous);
+    }
+
+    /// Set the [`TimestampEncoding`] [`Blockable::to_ipld`] will use the
+    /// next time this object is written through the generic store path
+    /// (e.g. [`crate::backend::Backend::put`]), so `Rfc3339` blocks are
+    /// reachable without calling [`Object::to_ipld_with_timestamp_encoding`]
+    /// directly.
+    pub fn set_timestamp_encoding(&mut self, encoding: TimestampEncoding) {
+        self.timestamp_encoding = encoding;
+    }
+
+    /* History */
+
+    /// Walk the revision chain formed by `previous` links, from this
+    /// `Object`'s immediate predecessor back to the root of the DAG.
+    pub fn history<'a>(&self, store: &'a Backend) -> History<'a> {
+        History {
+            store,
+            next: self.previous.clone(),
+        }
+    }
+
+    /// The number of revisions in this `Object`'s history, including itself.
+    pub fn version_count(&self, store: &Backend) -> Result<usize, ObjectHistoryError> {
+        let mut count = 1;
+        for object in self.history(store) {
+            object?;
+            count += 1;
+        }
+        Ok(count)
+    }
+}
+
+/// Iterator returned by [`Object::history`], walking `previous` links back to
+/// the root of the revision chain.
+pub struct History<'a> {
+    store: &'a Backend,
+    next: Option<Cid>,
+}
+
+impl<'a> Iterator for History<'a> {
+    type Item = Result<Object, ObjectHistoryError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let cid = self.next.take()?;
+        match self.store.get::<Object>(&cid) {
+            Ok(object) => {
+                self.next = object.previous().cloned();
+                Some(Ok(object))
+            }
+            Err(err) => Some(Err(err.into())),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ObjectHistoryError {
+    #[error("backend error: {0}")]
+    Backend(#[from] BackendError),
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -155,6 +458,199 @@ pub enum ObjectIpldError {
     MissingMapMember(String),
     #[error("serde json error: {0}")]
     SerdeJson(#[from] serde_json::Error),
+    #[error("invalid content type: {0}")]
+    InvalidContentType(#[from] mime::FromStrError),
+    #[error("content type is not a string: {0}")]
+    ContentTypeNotString(String),
+    #[error("invalid timestamp string: {0}")]
+    InvalidTimestampString(#[from] time::error::Parse),
     #[error("ipld data is not map")]
     NotMap,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const SAMPLE_CID: &str = "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi";
+
+    #[test]
+    fn round_trips_u64_near_max() {
+        let value = json!(u64::MAX);
+        assert_eq!(ipld_to_value(&value_to_ipld(&value)), value);
+    }
+
+    #[test]
+    fn round_trips_i64_min() {
+        let value = json!(i64::MIN);
+        assert_eq!(ipld_to_value(&value_to_ipld(&value)), value);
+    }
+
+    #[test]
+    fn round_trips_float() {
+        let value = json!(3.25);
+        assert_eq!(ipld_to_value(&value_to_ipld(&value)), value);
+    }
+
+    #[test]
+    fn round_trips_plain_object_and_array() {
+        let value = json!({ "author": "alice", "tags": ["a", "b"], "count": 2 });
+        assert_eq!(ipld_to_value(&value_to_ipld(&value)), value);
+    }
+
+    #[test]
+    fn escapes_cid_shaped_string_as_link() {
+        let value = json!(SAMPLE_CID);
+        let ipld = value_to_ipld(&value);
+        assert!(matches!(ipld, Ipld::Link(_)));
+        // Lossy by design: decoding a link always yields a bare CID string,
+        // so a plain CID-shaped string round-trips to itself here, but see
+        // `escapes_link_wrapper_object_to_bare_string` for the lossy case.
+        assert_eq!(ipld_to_value(&ipld), value);
+    }
+
+    #[test]
+    fn escapes_link_wrapper_object_to_bare_string() {
+        let value = json!({ "/": SAMPLE_CID });
+        let ipld = value_to_ipld(&value);
+        assert!(matches!(ipld, Ipld::Link(_)));
+        // The `{"/": ...}` wrapper shape is not reconstructed on decode.
+        assert_eq!(ipld_to_value(&ipld), json!(SAMPLE_CID));
+    }
+
+    #[test]
+    fn decodes_unix_nanos_and_rfc3339_timestamps_in_the_same_block() {
+        let object = Object::default();
+
+        // `to_ipld_with_timestamp_encoding(Rfc3339)` writes both timestamps
+        // as strings; swap `updated_at` back to the `UnixNanos` form so the
+        // block mixes both encodings and exercises the auto-detect in
+        // `decode_timestamp` for each variant independently.
+        let ipld = object
+            .to_ipld_with_timestamp_encoding(TimestampEncoding::Rfc3339)
+            .unwrap();
+        let mut map = match ipld {
+            Ipld::Map(map) => map,
+            _ => unreachable!(),
+        };
+        map.insert(
+            OBJECT_UPDATED_AT_LABEL.to_string(),
+            Ipld::Integer(object.updated_at().unix_timestamp_nanos()),
+        );
+
+        let decoded = Object::from_ipld(&Ipld::Map(map)).unwrap();
+        assert_eq!(decoded.created_at(), object.created_at());
+        assert_eq!(decoded.updated_at(), object.updated_at());
+    }
+
+    #[test]
+    fn set_timestamp_encoding_is_honored_by_the_generic_store_path() {
+        let store = Backend::default();
+
+        let mut object = Object::default();
+        object.set_timestamp_encoding(TimestampEncoding::Rfc3339);
+
+        let cid = store.put(&object).expect("put object");
+        let decoded: Object = store.get(&cid).expect("get object");
+
+        // `from_ipld` only detects `Rfc3339` if `created_at` was actually
+        // written as a string, so this confirms `Backend::put` — which only
+        // ever calls `Blockable::to_ipld`, never the inherent
+        // `to_ipld_with_timestamp_encoding` — wrote the block in Rfc3339
+        // form.
+        assert_eq!(decoded.timestamp_encoding(), TimestampEncoding::Rfc3339);
+        assert_eq!(decoded.created_at(), object.created_at());
+    }
+
+    #[test]
+    fn content_type_defaults_to_octet_stream_when_missing_or_empty() {
+        let object = Object::default();
+        let mut map = match object.to_ipld() {
+            Ipld::Map(map) => map,
+            _ => unreachable!(),
+        };
+
+        map.remove(OBJECT_CONTENT_TYPE_LABEL);
+        let decoded = Object::from_ipld(&Ipld::Map(map.clone())).unwrap();
+        assert_eq!(decoded.content_type(), &mime::APPLICATION_OCTET_STREAM);
+
+        map.insert(
+            OBJECT_CONTENT_TYPE_LABEL.to_string(),
+            Ipld::String(String::new()),
+        );
+        let decoded = Object::from_ipld(&Ipld::Map(map)).unwrap();
+        assert_eq!(decoded.content_type(), &mime::APPLICATION_OCTET_STREAM);
+    }
+
+    #[test]
+    fn content_type_round_trips_when_set() {
+        let mut object = Object::default();
+        object.update(None, None, Some(mime::IMAGE_PNG));
+
+        let decoded = Object::from_ipld(&object.to_ipld()).unwrap();
+        assert_eq!(decoded.content_type(), &mime::IMAGE_PNG);
+    }
+
+    #[test]
+    fn content_type_decode_errors_on_wrong_type() {
+        let object = Object::default();
+        let mut map = match object.to_ipld() {
+            Ipld::Map(map) => map,
+            _ => unreachable!(),
+        };
+
+        map.insert(OBJECT_CONTENT_TYPE_LABEL.to_string(), Ipld::Integer(1));
+        let err = Object::from_ipld(&Ipld::Map(map)).unwrap_err();
+        assert!(matches!(err, ObjectIpldError::ContentTypeNotString(_)));
+    }
+
+    #[test]
+    fn update_with_no_previous_leaves_history_empty() {
+        let store = Backend::default();
+
+        let mut genesis = Object::default();
+        genesis.update(None, Some(json!({"author": "alice"})), None);
+
+        assert_eq!(genesis.previous(), None);
+        assert!(genesis.history(&store).next().is_none());
+        assert_eq!(genesis.version_count(&store).unwrap(), 1);
+    }
+
+    #[test]
+    fn update_does_not_clear_an_existing_previous_link() {
+        let store = Backend::default();
+
+        let root = Object::default();
+        let root_cid = store.put(&root).expect("put root");
+
+        let mut child = Object::default();
+        child.link_previous(root_cid.clone());
+        child.update(None, None, Some(mime::IMAGE_PNG));
+
+        assert_eq!(child.previous(), Some(&root_cid));
+    }
+
+    #[test]
+    fn history_and_version_count_walk_the_previous_chain() {
+        let store = Backend::default();
+
+        let root = Object::default();
+        let root_cid = store.put(&root).expect("put root");
+
+        let mut child = Object::default();
+        child.link_previous(root_cid.clone());
+        let child_cid = store.put(&child).expect("put child");
+
+        let mut grandchild = Object::default();
+        grandchild.link_previous(child_cid.clone());
+
+        let history: Vec<Object> = grandchild
+            .history(&store)
+            .collect::<Result<_, _>>()
+            .expect("walk history");
+        assert_eq!(history, vec![child, root]);
+
+        assert_eq!(grandchild.version_count(&store).unwrap(), 3);
+    }
+}